@@ -68,6 +68,124 @@
 //! }
 //! ```
 //!
+//! ## Setup and teardown hooks
+//!
+//! Often you don't need a full higher-order wrapper and just want a little
+//! setup and teardown around each test. For that you can pass plain `before`
+//! and `after` functions instead of (or alongside) a wrapper. The `after` hook
+//! runs even if the test panics:
+//!
+//! ```
+//! #[wraptest::wrap_tests(before = log_before, after = log_after)]
+//! mod tests {
+//!     fn log_before() {
+//!         eprintln!("--before--");
+//!     }
+//!
+//!     fn log_after() {
+//!         eprintln!("--after--");
+//!     }
+//!
+//!     #[test]
+//!     fn basic() {
+//!         eprintln!("in basic");
+//!     }
+//! }
+//! ```
+//!
+//! When combined with `wrapper`/`async_wrapper`, the hooks run inside the
+//! wrapper's closure.
+//!
+//! ## Built-in tracing setup
+//!
+//! If all you want is a `tracing` subscriber that prints to the test output,
+//! you can skip the wrapper entirely and let the macro install one for you with
+//! `init_tracing`. The filter is read from `RUST_LOG` at runtime, falling back
+//! to the directive you pass:
+//!
+//! ```
+//! #[wraptest::wrap_tests(init_tracing = "debug")]
+//! mod tests {
+//!     use tracing::info;
+//!
+//!     #[test]
+//!     fn with_tracing() {
+//!         info!("with tracing!");
+//!     }
+//! }
+//! ```
+//!
+//! ## Other async runtimes
+//!
+//! By default `#[test]` and `#[tokio::test]` are recognized. If your async
+//! tests run under a different runtime you can teach the macro about its test
+//! attribute with `test_attrs`:
+//!
+//! ```ignore
+//! #[wraptest::wrap_tests(async_wrapper = with_logs, test_attrs = [async_std::test])]
+//! mod tests {
+//!     // ...
+//! }
+//! ```
+//!
+//! ## Per-test spans
+//!
+//! Pass `instrument` to wrap every test in a `tracing` span named after the
+//! test function, so its events are grouped under a span in your logs without
+//! annotating each test by hand:
+//!
+//! ```
+//! #[wraptest::wrap_tests(init_tracing = "debug", instrument)]
+//! mod tests {
+//!     use tracing::info;
+//!
+//!     #[test]
+//!     fn basic() {
+//!         info!("inside the `basic` span");
+//!     }
+//! }
+//! ```
+//!
+//! ## Fixtures
+//!
+//! A wrapper can also hand a value to each test. If a test declares a single
+//! argument, the macro passes the wrapper's setup value through to it, turning
+//! wraptest into a lightweight way to share a database handle, temp dir, or
+//! mock server across tests:
+//!
+//! ```
+//! #[wraptest::wrap_tests(wrapper = with_db)]
+//! mod tests {
+//!     struct Db;
+//!
+//!     fn with_db<T, R>(test_fn: T) -> R
+//!     where
+//!         T: FnOnce(Db) -> R,
+//!     {
+//!         let db = Db;
+//!         test_fn(db)
+//!     }
+//!
+//!     #[test]
+//!     fn uses_db(_db: Db) {
+//!         // ...
+//!     }
+//! }
+//! ```
+//!
+//! A fixture needs a wrapper to supply it, so a test with an argument but no
+//! `wrapper`/`async_wrapper` is rejected:
+//!
+//! ```compile_fail
+//! #[wraptest::wrap_tests(before = log_before)]
+//! mod tests {
+//!     fn log_before() {}
+//!
+//!     #[test]
+//!     fn uses_db(_db: u8) {}
+//! }
+//! ```
+//!
 //! ## Custom return type
 //!
 //! If you want to return something other than `()` from your tests you just
@@ -111,7 +229,7 @@ use syn::{
     parse_macro_input, parse_quote,
     punctuated::Punctuated,
     visit_mut::{self, VisitMut},
-    Ident, ItemFn, ItemMod, Token,
+    Ident, ItemFn, ItemMod, LitStr, Path, Stmt, Token,
 };
 
 const USAGE: &str = "Usage is generally `#[wraptest::wrap_tests(wrapper = your_fn)]`, or
@@ -121,6 +239,11 @@ you have async tests.";
 struct Args {
     wrapper: Option<Ident>,
     async_wrapper: Option<Ident>,
+    before: Option<Ident>,
+    after: Option<Ident>,
+    init_tracing: Option<Option<LitStr>>,
+    test_attrs: Vec<Path>,
+    instrument: bool,
 }
 
 impl Parse for Args {
@@ -129,17 +252,32 @@ impl Parse for Args {
 
         let mut wrapper = None;
         let mut async_wrapper = None;
+        let mut before = None;
+        let mut after = None;
+        let mut init_tracing = None;
+        let mut test_attrs = Vec::new();
+        let mut instrument = false;
 
         for pair in punct.into_pairs() {
             match pair.into_value() {
                 Arg::Wrapper(ident) => wrapper = Some(ident),
                 Arg::AsyncWrapper(ident) => async_wrapper = Some(ident),
+                Arg::Before(ident) => before = Some(ident),
+                Arg::After(ident) => after = Some(ident),
+                Arg::InitTracing(filter) => init_tracing = Some(filter),
+                Arg::TestAttrs(paths) => test_attrs.extend(paths),
+                Arg::Instrument => instrument = true,
             }
         }
 
         Ok(Self {
             wrapper,
             async_wrapper,
+            before,
+            after,
+            init_tracing,
+            test_attrs,
+            instrument,
         })
     }
 }
@@ -147,18 +285,58 @@ impl Parse for Args {
 enum Arg {
     Wrapper(Ident),
     AsyncWrapper(Ident),
+    Before(Ident),
+    After(Ident),
+    /// `init_tracing` on its own, or `init_tracing = "<fallback directive>"`.
+    InitTracing(Option<LitStr>),
+    /// `test_attrs = [async_std::test, actix_rt::test]`.
+    TestAttrs(Vec<Path>),
+    /// `instrument` — wrap each test in a span named after it.
+    Instrument,
 }
 
 impl Parse for Arg {
     fn parse(input: ParseStream) -> syn::Result<Self> {
         let name = input.parse::<Ident>()?;
+
+        // `init_tracing` can appear bare or with a string literal giving the
+        // fallback filter directive.
+        if name == "init_tracing" {
+            let filter = if input.peek(Token![=]) {
+                input.parse::<Token![=]>()?;
+                Some(input.parse::<LitStr>()?)
+            } else {
+                None
+            };
+            return Ok(Self::InitTracing(filter));
+        }
+
+        // `instrument` is a bare flag with no value.
+        if name == "instrument" {
+            return Ok(Self::Instrument);
+        }
+
         input.parse::<Token![=]>()?;
+
+        // `test_attrs` takes a bracketed list of attribute paths rather than a
+        // single identifier.
+        if name == "test_attrs" {
+            let content;
+            syn::bracketed!(content in input);
+            let paths = Punctuated::<Path, Token![,]>::parse_terminated(&content)?;
+            return Ok(Self::TestAttrs(paths.into_iter().collect()));
+        }
+
         let value = input.parse::<Ident>()?;
 
         let arg = if name == "wrapper" {
             Self::Wrapper(value)
         } else if name == "async_wrapper" {
             Self::AsyncWrapper(value)
+        } else if name == "before" {
+            Self::Before(value)
+        } else if name == "after" {
+            Self::After(value)
         } else {
             abort!(name, "wraptest: Unexpected parameter"; note = USAGE)
         };
@@ -176,12 +354,22 @@ pub fn wrap_tests(
     let Args {
         wrapper,
         async_wrapper,
+        before,
+        after,
+        init_tracing,
+        test_attrs,
+        instrument,
     } = parse_macro_input!(args as Args);
     let mut module = parse_macro_input!(input as ItemMod);
 
     let mut visitor = ModVisitor {
         wrapper,
         async_wrapper,
+        before,
+        after,
+        init_tracing,
+        test_attrs,
+        instrument,
     };
     visitor.visit_item_mod_mut(&mut module);
 
@@ -192,15 +380,21 @@ pub fn wrap_tests(
 struct ModVisitor {
     wrapper: Option<Ident>,
     async_wrapper: Option<Ident>,
+    before: Option<Ident>,
+    after: Option<Ident>,
+    init_tracing: Option<Option<LitStr>>,
+    test_attrs: Vec<Path>,
+    instrument: bool,
 }
 
 impl VisitMut for ModVisitor {
     fn visit_item_fn_mut(&mut self, node: &mut ItemFn) {
         if self.is_test_fn(node) {
-            if !node.sig.inputs.is_empty() {
+            if node.sig.inputs.len() > 1 {
                 abort!(
                     node.sig.inputs,
-                    "wraptest: Test functions that take arguments aren't supported";
+                    "wraptest: Test functions may take at most one argument (a fixture \
+                     supplied by the wrapper)";
                     note = USAGE,
                 );
             }
@@ -219,52 +413,184 @@ impl ModVisitor {
                 return true;
             }
 
-            let pairs = attr
-                .path
-                .segments
-                .pairs()
-                .map(|pair| pair.value().ident.to_string())
-                .collect::<Vec<_>>();
-            if pairs.len() == 2 && pairs[0] == "tokio" && pairs[1] == "test" {
+            let segments = Self::path_segments(&attr.path);
+            if segments.len() == 2 && segments[0] == "tokio" && segments[1] == "test" {
                 return true;
             }
 
-            false
+            self.test_attrs
+                .iter()
+                .any(|path| Self::path_segments(path) == segments)
         })
     }
 
+    fn path_segments(path: &Path) -> Vec<String> {
+        path.segments
+            .pairs()
+            .map(|pair| pair.value().ident.to_string())
+            .collect()
+    }
+
     fn visit_test_fn(&mut self, node: &mut ItemFn) {
         let wrapped = Self::strip_attrs(node);
         let name = &wrapped.sig.ident;
 
+        // A test with a single argument opts into fixture injection: the
+        // wrapper produces a value and hands it to the test. The value flows in
+        // through the closure we pass to the wrapper, so fixtures require one.
+        let fixture = !node.sig.inputs.is_empty();
+        if fixture
+            && ((node.sig.asyncness.is_some() && self.async_wrapper.is_none())
+                || (node.sig.asyncness.is_none() && self.wrapper.is_none()))
+        {
+            abort!(
+                node.sig.inputs,
+                "wraptest: Tests that take a fixture argument require a matching \
+                 `wrapper`/`async_wrapper` to supply it";
+                note = USAGE,
+            );
+        }
+        let closure_param = if fixture {
+            quote! { __wraptest_fixture }
+        } else {
+            quote! {}
+        };
+        let call_args = closure_param.clone();
+
+        // The fixture parameter belongs only on the inner `#wrapped` clone (made
+        // above); libtest rejects a `#[test]` fn that takes arguments, so strip
+        // it from the outer function we emit.
+        node.sig.inputs.clear();
+
+        let has_hooks = self.before.is_some() || self.after.is_some();
+
+        // The `before`/`after` hooks run immediately around the inner test
+        // call. `after` is emitted as the `Drop` impl of a small RAII guard so
+        // that teardown fires even when the test panics or returns early.
+        let before_stmt = self.before.as_ref().map(|before| quote! { #before(); });
+        let (guard_def, guard_let) = match &self.after {
+            Some(after) => (
+                quote! {
+                    struct AfterGuard;
+                    impl ::core::ops::Drop for AfterGuard {
+                        fn drop(&mut self) {
+                            #after();
+                        }
+                    }
+                },
+                quote! { let __wraptest_after_guard = AfterGuard; },
+            ),
+            None => (quote! {}, quote! {}),
+        };
+
+        // `instrument` wraps the test in a span named after it. For sync tests
+        // an entered guard around the body is enough; async tests need the span
+        // attached to the future, because an entered guard doesn't stay active
+        // across `.await` points.
+        let span_name = name.to_string();
+        let span_enter = if self.instrument {
+            quote! { let _span = tracing::info_span!(#span_name).entered(); }
+        } else {
+            quote! {}
+        };
+        let async_call = if self.instrument {
+            quote! {
+                tracing::Instrument::instrument(#name(#call_args), tracing::info_span!(#span_name))
+                    .await
+            }
+        } else {
+            quote! { #name(#call_args).await }
+        };
+
+        // Whether the test body needs to be decorated at all. When it doesn't,
+        // we keep the original `wrapper(test_fn)` shape for backwards
+        // compatibility; otherwise we pass a closure that runs the decorations
+        // inside the wrapper.
+        let decorated = has_hooks || self.instrument || self.init_tracing.is_some();
+
+        let sync_body = quote! {
+            #span_enter
+            #before_stmt
+            #guard_let
+            #name(#call_args)
+        };
+        let async_body = quote! {
+            #before_stmt
+            #guard_let
+            #async_call
+        };
+
         node.block.stmts = if node.sig.asyncness.is_some() {
-            let async_wrapper = match &self.async_wrapper {
-                Some(wrapper) => wrapper,
+            match &self.async_wrapper {
+                Some(async_wrapper) if decorated => parse_quote! {
+                    #wrapped
+                    #guard_def
+                    #async_wrapper(|#closure_param| async move { #async_body }).await
+                },
+                Some(async_wrapper) => parse_quote! {
+                    #wrapped
+                    #async_wrapper(#name).await
+                },
+                None if decorated => parse_quote! {
+                    #wrapped
+                    #guard_def
+                    #async_body
+                },
                 None => abort!(
                     node,
                     "wraptest: Must specify `async_wrapper` to wrap async test functions";
                     note = USAGE
                 ),
-            };
-
-            parse_quote! {
-                #wrapped
-                #async_wrapper(#name).await
             }
         } else {
-            let wrapper = match &self.wrapper {
-                Some(wrapper) => wrapper,
+            match &self.wrapper {
+                Some(wrapper) if decorated => parse_quote! {
+                    #wrapped
+                    #guard_def
+                    #wrapper(|#closure_param| { #sync_body })
+                },
+                Some(wrapper) => parse_quote! {
+                    #wrapped
+                    #wrapper(#name)
+                },
+                None if decorated => parse_quote! {
+                    #wrapped
+                    #guard_def
+                    #sync_body
+                },
                 None => abort!(
                     node,
-                    "wraptest: Must specify `wrapper` to wrap async test functions";
+                    "wraptest: Must specify `wrapper` to wrap sync test functions";
                     note = USAGE
                 ),
-            };
-            parse_quote! {
-                #wrapped
-                #wrapper(#name)
             }
         };
+
+        // `init_tracing` installs a fresh `fmt` subscriber scoped to each test,
+        // so users don't have to hand-write a `with_logs` wrapper. The default
+        // filter is read from `RUST_LOG` at runtime, falling back to the
+        // directive given to the macro (if any). The guard is bound into the
+        // test block so it stays alive for the whole test body.
+        if let Some(fallback) = &self.init_tracing {
+            let filter = match fallback {
+                Some(directive) => quote! {
+                    tracing_subscriber::EnvFilter::try_from_default_env()
+                        .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new(#directive))
+                },
+                None => quote! { tracing_subscriber::EnvFilter::from_default_env() },
+            };
+
+            let mut stmts: Vec<Stmt> = parse_quote! {
+                let __wraptest_subscriber = tracing_subscriber::fmt()
+                    .with_env_filter(#filter)
+                    .with_test_writer()
+                    .finish();
+                let __wraptest_tracing_guard =
+                    tracing::subscriber::set_default(__wraptest_subscriber);
+            };
+            stmts.append(&mut node.block.stmts);
+            node.block.stmts = stmts;
+        }
     }
 
     fn strip_attrs(node: &ItemFn) -> ItemFn {
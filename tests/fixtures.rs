@@ -0,0 +1,36 @@
+#[cfg(test)]
+#[wraptest::wrap_tests(wrapper = with_db, async_wrapper = with_db_async)]
+mod tests {
+    use std::future::Future;
+
+    struct Db {
+        connected: bool,
+    }
+
+    fn with_db<T, R>(test_fn: T) -> R
+    where
+        T: FnOnce(Db) -> R,
+    {
+        let db = Db { connected: true };
+        test_fn(db)
+    }
+
+    async fn with_db_async<T, F, R>(test_fn: T) -> R
+    where
+        T: FnOnce(Db) -> F,
+        F: Future<Output = R>,
+    {
+        let db = Db { connected: true };
+        test_fn(db).await
+    }
+
+    #[test]
+    fn sync_fixture(db: Db) {
+        assert!(db.connected);
+    }
+
+    #[tokio::test]
+    async fn async_fixture(db: Db) {
+        assert!(db.connected);
+    }
+}
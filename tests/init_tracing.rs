@@ -0,0 +1,24 @@
+#[cfg(test)]
+#[wraptest::wrap_tests(init_tracing = "debug")]
+mod tests {
+    use std::time::Duration;
+    use tracing::info;
+
+    #[test]
+    fn basic() {
+        info!("in basic");
+    }
+
+    #[tokio::test]
+    async fn basic_async() {
+        info!("in basic async");
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        info!("finishing basic async");
+    }
+
+    #[test]
+    fn returns_result() -> Result<(), String> {
+        info!("returns result");
+        Ok(())
+    }
+}
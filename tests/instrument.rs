@@ -0,0 +1,18 @@
+#[cfg(test)]
+#[wraptest::wrap_tests(init_tracing = "debug", instrument)]
+mod tests {
+    use std::time::Duration;
+    use tracing::info;
+
+    #[test]
+    fn basic() {
+        info!("inside the basic span");
+    }
+
+    #[tokio::test]
+    async fn basic_async() {
+        info!("inside the basic_async span");
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        info!("still inside the span after awaiting");
+    }
+}
@@ -0,0 +1,21 @@
+#[cfg(test)]
+#[wraptest::wrap_tests(async_wrapper = with_setup, test_attrs = [async_std::test])]
+mod tests {
+    use std::future::Future;
+
+    async fn with_setup<T, F, R>(test_fn: T) -> R
+    where
+        T: FnOnce() -> F,
+        F: Future<Output = R>,
+    {
+        eprintln!("before");
+        let result = test_fn().await;
+        eprintln!("after");
+        result
+    }
+
+    #[async_std::test]
+    async fn async_std_test() {
+        assert!(true);
+    }
+}